@@ -0,0 +1,102 @@
+//! Asynchronous configuration sources. This module only exists if the Cargo
+//! feature `async` is enabled.
+//!
+//! [`AsyncSource`] mirrors the synchronous [`Source`][crate::Source] trait but
+//! loads its partial config inside an `async fn`, so configuration can be
+//! pulled from a config server or object store without blocking the runtime.
+
+use async_trait::async_trait;
+
+use crate::{
+    error::{Error, ErrorInner},
+    format::FormatRegistry,
+    Partial,
+};
+
+/// A source of configuration values that is loaded asynchronously.
+///
+/// This is the async counterpart to [`Source`][crate::Source]; see
+/// [`Config::from_sources_async`][crate::Config::from_sources_async] for how
+/// sources are combined.
+#[async_trait]
+pub trait AsyncSource<P: Partial> {
+    /// Attempts to load the configuration from this source.
+    async fn load(&self) -> Result<P, Error>;
+}
+
+/// Fetches configuration text over HTTP(S) from a URL.
+///
+/// The format is detected from the URL's file extension, falling back to the
+/// response's `Content-Type`. Transport failures surface as
+/// [`ErrorInner::Io`] with `path: None`; parse failures as
+/// [`ErrorInner::Deserialization`].
+pub struct Http {
+    url: String,
+    registry: FormatRegistry,
+}
+
+impl Http {
+    /// Creates a source fetching from `url`, using the built-in format
+    /// handlers enabled via Cargo features.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), registry: FormatRegistry::with_builtins() }
+    }
+
+    /// Overrides the format registry used to parse the fetched body.
+    pub fn with_registry(mut self, registry: FormatRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    fn extension(&self) -> Option<&str> {
+        let segment = self.url.rsplit('/').next()?;
+        // Drop any query string or fragment so that e.g.
+        // `config.toml?v=2` yields `toml`, not `toml?v=2`.
+        let segment = segment.split(['?', '#']).next()?;
+        segment.rsplit_once('.').map(|(_, ext)| ext)
+    }
+}
+
+#[async_trait]
+impl<P: Partial> AsyncSource<P> for Http {
+    async fn load(&self) -> Result<P, Error> {
+        let to_io = |err: reqwest::Error| -> Error {
+            ErrorInner::Io {
+                path: None,
+                err: std::io::Error::new(std::io::ErrorKind::Other, err),
+            }
+            .into()
+        };
+
+        let response = reqwest::get(&self.url).await.map_err(to_io)?;
+        let response = response.error_for_status().map_err(to_io)?;
+        let ext = self
+            .extension()
+            .or_else(|| content_type_extension(&response))
+            .ok_or_else(|| {
+                ErrorInner::Deserialization {
+                    source: Some(format!("URL '{}'", self.url)),
+                    err: "could not determine configuration format from URL or Content-Type".into(),
+                }
+                .into()
+            })?
+            .to_owned();
+        let raw = response.text().await.map_err(to_io)?;
+
+        let source = format!("URL '{}'", self.url);
+        let format = self.registry.get(&ext).ok_or_else(|| {
+            Error::from(ErrorInner::UnsupportedFileFormat { path: self.url.as_str().into() })
+        })?;
+        crate::format::deserialize_with(format, &raw, &source)
+    }
+}
+
+fn content_type_extension(response: &reqwest::Response) -> Option<&'static str> {
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE)?.to_str().ok()?;
+    match content_type.split(';').next()?.trim() {
+        "application/toml" | "text/toml" => Some("toml"),
+        "application/yaml" | "text/yaml" | "application/x-yaml" => Some("yaml"),
+        "application/json" => Some("json"),
+        _ => None,
+    }
+}
@@ -0,0 +1,164 @@
+//! Deserializing environment variables into leaf fields, including delimited
+//! collections.
+//!
+//! The code generated for a `#[config(env = "...")]` field calls [`from_env`]
+//! with the [`Env`] descriptor recorded in the field's [`Meta`][crate::meta].
+//! When `env_separator` (and optionally `env_kv_separator`) are set, the raw
+//! value is split and each element is deserialized into the target collection's
+//! element type; otherwise the value is deserialized as a single scalar.
+
+use serde::de::{
+    value::{Error as ValueError, MapAccessDeserializer, SeqAccessDeserializer},
+    DeserializeOwned, DeserializeSeed, Error as _, IntoDeserializer, MapAccess, SeqAccess,
+};
+
+use crate::{
+    error::{Error, ErrorInner},
+    meta::Env,
+};
+
+/// Deserializes the raw value of an environment variable into `T`, honoring the
+/// list/map separators recorded in `env`.
+///
+/// A failure is reported as [`ErrorInner::EnvDeserialization`] whose `msg`
+/// includes the offending element when splitting is in effect.
+pub(crate) fn from_env<T>(field: &str, raw: &str, env: &Env) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    let result: Result<T, ValueError> = match (env.separator, env.kv_separator) {
+        (None, _) => T::deserialize(raw.into_deserializer()),
+        (Some(sep), None) => {
+            T::deserialize(SeqAccessDeserializer::new(SeqEnv { elems: raw.split(sep) }))
+        }
+        (Some(sep), Some(kv)) => T::deserialize(MapAccessDeserializer::new(MapEnv {
+            entries: raw.split(sep),
+            kv,
+            value: None,
+        })),
+    };
+
+    result.map_err(|err: ValueError| {
+        ErrorInner::EnvDeserialization {
+            field: field.to_owned(),
+            key: env.var.to_owned(),
+            msg: err.to_string(),
+        }
+        .into()
+    })
+}
+
+/// `SeqAccess` over the pieces of a separator-split env value.
+struct SeqEnv<I> {
+    elems: I,
+}
+
+impl<'de, 'a, I> SeqAccess<'de> for SeqEnv<I>
+where
+    I: Iterator<Item = &'a str>,
+{
+    type Error = ValueError;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        match self.elems.next() {
+            None => Ok(None),
+            Some(piece) => seed
+                .deserialize(piece.into_deserializer())
+                .map(Some)
+                .map_err(|err: ValueError| ValueError::custom(format!("`{}`: {}", piece, err))),
+        }
+    }
+}
+
+/// `MapAccess` over the `key<kv>value` pieces of a separator-split env value.
+struct MapEnv<'a, I> {
+    entries: I,
+    kv: &'a str,
+    value: Option<&'a str>,
+}
+
+impl<'de, 'a, I> MapAccess<'de> for MapEnv<'a, I>
+where
+    I: Iterator<Item = &'a str>,
+{
+    type Error = ValueError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            None => Ok(None),
+            Some(entry) => {
+                let (key, value) = entry.split_once(self.kv).ok_or_else(|| {
+                    ValueError::custom(format!(
+                        "`{}`: missing key/value separator `{}`",
+                        entry, self.kv
+                    ))
+                })?;
+                self.value = Some(value);
+                seed.deserialize(key.into_deserializer())
+                    .map(Some)
+                    .map_err(|err: ValueError| ValueError::custom(format!("`{}`: {}", key, err)))
+            }
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self.value.take().expect("next_value_seed called before next_key_seed");
+        seed.deserialize(value.into_deserializer())
+            .map_err(|err: ValueError| ValueError::custom(format!("`{}`: {}", value, err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::from_env;
+    use crate::meta::Env;
+
+    fn env(separator: Option<&'static str>, kv_separator: Option<&'static str>) -> Env {
+        Env { var: "VAR", separator, kv_separator }
+    }
+
+    #[test]
+    fn scalar_without_separator() {
+        let value: String = from_env("field", "hello", &env(None, None)).unwrap();
+        assert_eq!(value, "hello");
+    }
+
+    #[test]
+    fn splits_into_vec() {
+        let value: Vec<String> =
+            from_env("field", "a,b,c", &env(Some(","), None)).unwrap();
+        assert_eq!(value, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn splits_into_map() {
+        let value: HashMap<String, String> =
+            from_env("field", "a=1;b=2", &env(Some(";"), Some("="))).unwrap();
+        assert_eq!(value.get("a").map(String::as_str), Some("1"));
+        assert_eq!(value.get("b").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn element_failure_names_offending_element() {
+        let err = from_env::<Vec<char>>("field", "a,bc,d", &env(Some(","), None)).unwrap_err();
+        assert!(err.to_string().contains("`bc`"), "{err}");
+    }
+
+    #[test]
+    fn map_entry_without_kv_separator_names_element() {
+        let err = from_env::<HashMap<String, String>>("field", "a=1,boom", &env(Some(","), Some("=")))
+            .unwrap_err();
+        assert!(err.to_string().contains("`boom`"), "{err}");
+    }
+}
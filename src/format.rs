@@ -0,0 +1,224 @@
+//! Pluggable file-format support.
+//!
+//! Out of the box, confique understands TOML, YAML and JSON (each behind its
+//! respective Cargo feature). The [`Format`] trait and [`FormatRegistry`] let
+//! applications teach confique additional formats (INI, HCL, a bespoke
+//! `.conf`, ...) that it does not ship itself. A registry is consulted by the
+//! `Source` impls for `Path`/`PathBuf` to pick a parser based on the file
+//! extension.
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::error::{Error, ErrorInner};
+
+/// A configuration file format confique can load.
+///
+/// Implementors turn raw file contents into a type-erased `serde` deserializer
+/// that is subsequently driven to produce the partial config type. Returning a
+/// deserializer rather than a fixed intermediate value keeps loading lossless:
+/// format-native types that have no JSON representation (TOML datetimes, for
+/// example) survive straight into the target type. Deserialization failures are
+/// surfaced via [`Error`] carrying [`ErrorInner::Deserialization`] with a
+/// descriptive `source` string.
+pub trait Format: Send + Sync {
+    /// The lower-case file extensions (without leading dot) this format is
+    /// responsible for, e.g. `&["yaml", "yml"]`.
+    fn extensions(&self) -> &[&str];
+
+    /// Turns the raw file contents into a type-erased deserializer.
+    ///
+    /// `source` is a human readable description of where `raw` came from (e.g.
+    /// `file 'config.ini'`) and should be threaded into a resulting
+    /// [`ErrorInner::Deserialization`] error if the format fails to set up the
+    /// deserializer eagerly.
+    fn parse<'de>(
+        &self,
+        raw: &'de str,
+        source: &str,
+    ) -> Result<Box<dyn erased_serde::Deserializer<'de> + 'de>, Error>;
+}
+
+/// A mapping from file extension to [`Format`] handler.
+///
+/// Populate a registry before loading a `Path` source to route otherwise
+/// [`ErrorInner::UnsupportedFileFormat`] extensions through a custom parser.
+/// [`FormatRegistry::with_builtins`] pre-registers every format enabled via
+/// Cargo features, so the default behavior is unchanged when nobody customizes
+/// it.
+#[derive(Default)]
+pub struct FormatRegistry {
+    by_extension: HashMap<String, Arc<dyn Format>>,
+}
+
+impl FormatRegistry {
+    /// Creates an empty registry without any built-in formats.
+    pub fn new() -> Self {
+        Self { by_extension: HashMap::new() }
+    }
+
+    /// Creates a registry pre-populated with all built-in formats enabled via
+    /// Cargo features.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        #[cfg(feature = "toml")]
+        registry.register(Arc::new(builtin::Toml));
+        #[cfg(feature = "yaml")]
+        registry.register(Arc::new(builtin::Yaml));
+        #[cfg(feature = "json5")]
+        registry.register(Arc::new(builtin::Json));
+        #[cfg(feature = "ron")]
+        registry.register(Arc::new(builtin::Ron));
+        registry
+    }
+
+    /// Registers `format`, mapping all of its extensions to it. An extension
+    /// already present is overwritten, letting callers override a built-in.
+    pub fn register(&mut self, format: Arc<dyn Format>) {
+        for ext in format.extensions() {
+            self.by_extension.insert(ext.to_lowercase(), Arc::clone(&format));
+        }
+    }
+
+    /// Looks up the handler responsible for `extension` (matched
+    /// case-insensitively).
+    pub fn get(&self, extension: &str) -> Option<&dyn Format> {
+        self.by_extension.get(&extension.to_lowercase()).map(|f| &**f)
+    }
+}
+
+/// Parses `raw` with `format` and deserializes the result into `T`, mapping any
+/// failure to [`ErrorInner::Deserialization`] carrying `source`.
+pub(crate) fn deserialize_with<T>(
+    format: &dyn Format,
+    raw: &str,
+    source: &str,
+) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let mut de = format.parse(raw, source)?;
+    erased_serde::deserialize(&mut de).map_err(|e| deser_error(source, e))
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod tests {
+    use super::{deserialize_with, FormatRegistry};
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Sample {
+        name: String,
+        port: u16,
+    }
+
+    #[test]
+    fn toml_roundtrips_through_erased_deserializer() {
+        let registry = FormatRegistry::with_builtins();
+        let toml = registry.get("toml").expect("toml built-in registered");
+        let parsed: Sample =
+            deserialize_with(toml, "name = \"site\"\nport = 8080\n", "test").unwrap();
+        assert_eq!(parsed, Sample { name: "site".to_owned(), port: 8080 });
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn ron_is_registered() {
+        let registry = FormatRegistry::with_builtins();
+        assert!(registry.get("ron").is_some());
+    }
+}
+
+/// Convenience for building the `source` string and mapping a parser error to
+/// [`ErrorInner::Deserialization`].
+pub(crate) fn deser_error(
+    source: &str,
+    err: impl std::error::Error + Send + Sync + 'static,
+) -> Error {
+    ErrorInner::Deserialization {
+        source: Some(source.to_owned()),
+        err: Box::new(err),
+    }
+    .into()
+}
+
+mod builtin {
+    #[cfg(feature = "toml")]
+    pub(super) struct Toml;
+
+    #[cfg(feature = "toml")]
+    impl super::Format for Toml {
+        fn extensions(&self) -> &[&str] {
+            &["toml"]
+        }
+        fn parse<'de>(
+            &self,
+            raw: &'de str,
+            _source: &str,
+        ) -> Result<Box<dyn erased_serde::Deserializer<'de> + 'de>, super::Error> {
+            // Deserialize straight from the TOML deserializer so native types
+            // (datetimes, ...) reach the target type untouched; parse errors
+            // surface when the partial is driven.
+            let de = toml::Deserializer::new(raw);
+            Ok(Box::new(<dyn erased_serde::Deserializer>::erase(de)))
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    pub(super) struct Yaml;
+
+    #[cfg(feature = "yaml")]
+    impl super::Format for Yaml {
+        fn extensions(&self) -> &[&str] {
+            &["yaml", "yml"]
+        }
+        fn parse<'de>(
+            &self,
+            raw: &'de str,
+            _source: &str,
+        ) -> Result<Box<dyn erased_serde::Deserializer<'de> + 'de>, super::Error> {
+            let de = serde_yaml::Deserializer::from_str(raw);
+            Ok(Box::new(<dyn erased_serde::Deserializer>::erase(de)))
+        }
+    }
+
+    #[cfg(feature = "json5")]
+    pub(super) struct Json;
+
+    #[cfg(feature = "json5")]
+    impl super::Format for Json {
+        fn extensions(&self) -> &[&str] {
+            &["json", "json5"]
+        }
+        fn parse<'de>(
+            &self,
+            raw: &'de str,
+            source: &str,
+        ) -> Result<Box<dyn erased_serde::Deserializer<'de> + 'de>, super::Error> {
+            use serde::de::IntoDeserializer;
+
+            // json5 has no streaming deserializer, but its value model *is*
+            // JSON, so routing through `serde_json::Value` is lossless here.
+            let value: serde_json::Value =
+                json5::from_str(raw).map_err(|e| super::deser_error(source, e))?;
+            let de = value.into_deserializer();
+            Ok(Box::new(<dyn erased_serde::Deserializer>::erase(de)))
+        }
+    }
+
+    #[cfg(feature = "ron")]
+    pub(super) struct Ron;
+
+    #[cfg(feature = "ron")]
+    impl super::Format for Ron {
+        fn extensions(&self) -> &[&str] {
+            crate::ron::FILE_EXTENSIONS
+        }
+        fn parse<'de>(
+            &self,
+            raw: &'de str,
+            source: &str,
+        ) -> Result<Box<dyn erased_serde::Deserializer<'de> + 'de>, super::Error> {
+            let de = ron::Deserializer::from_str(raw).map_err(|e| super::deser_error(source, e))?;
+            Ok(Box::new(<dyn erased_serde::Deserializer>::erase(de)))
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! JSON-Schema generation. This module only exists if the Cargo feature
+//! `json-schema` is enabled.
+//!
+//! [`generate`] walks a [`Meta`] tree and emits a Draft-2020-12 JSON Schema
+//! describing the configuration's shape, so editors can validate and
+//! autocomplete the corresponding TOML/YAML/JSON files.
+
+use serde_json::{Map, Value};
+
+use crate::meta::{Expr, Field, FieldKind, LeafKind, Meta};
+
+
+const SCHEMA_URI: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generates a Draft-2020-12 JSON Schema from the given meta data.
+pub fn generate(meta: &Meta) -> Value {
+    let mut schema = object_schema(meta);
+    if let Value::Object(map) = &mut schema {
+        map.insert("$schema".into(), SCHEMA_URI.into());
+        map.insert("title".into(), meta.name.into());
+    }
+    schema
+}
+
+/// Builds the `object` schema for a (nested or root) `Meta`.
+fn object_schema(meta: &Meta) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for field in meta.fields {
+        properties.insert(field.name.to_owned(), field_schema(field));
+        if is_required(field) {
+            required.push(Value::from(field.name));
+        }
+    }
+
+    let mut schema = Map::new();
+    schema.insert("type".into(), "object".into());
+    if let Some(doc) = join_doc(meta.doc) {
+        schema.insert("description".into(), doc.into());
+    }
+    schema.insert("properties".into(), Value::Object(properties));
+    if !required.is_empty() {
+        schema.insert("required".into(), Value::Array(required));
+    }
+    Value::Object(schema)
+}
+
+fn field_schema(field: &Field) -> Value {
+    let mut schema = match &field.kind {
+        FieldKind::Nested { meta } => object_schema(meta),
+        FieldKind::Leaf { kind, .. } => leaf_schema(kind),
+    };
+
+    if let Some(doc) = join_doc(field.doc) {
+        if let Value::Object(map) = &mut schema {
+            // Keep the field's own description ahead of any inherited one from a
+            // nested struct's meta doc.
+            map.insert("description".into(), doc.into());
+        }
+    }
+    schema
+}
+
+fn leaf_schema(kind: &LeafKind) -> Value {
+    let mut schema = Map::new();
+    if let LeafKind::Required { default: Some(expr) } = kind {
+        if let Some(ty) = type_of(expr) {
+            schema.insert("type".into(), ty.into());
+        }
+        // `Expr` implements `Serialize` (including the ordered-map serializer),
+        // so the default round-trips into the schema verbatim.
+        if let Ok(default) = serde_json::to_value(expr) {
+            schema.insert("default".into(), default);
+        }
+    }
+    Value::Object(schema)
+}
+
+/// A field is required iff it is a leaf with a non-`Option` type and no default.
+fn is_required(field: &Field) -> bool {
+    matches!(
+        field.kind,
+        FieldKind::Leaf { kind: LeafKind::Required { default: None }, .. }
+    )
+}
+
+/// Maps a default expression to its JSON-Schema `type`, where inferrable.
+fn type_of(expr: &Expr) -> Option<&'static str> {
+    match expr {
+        Expr::Str(_) => Some("string"),
+        Expr::Float(_) => Some("number"),
+        Expr::Integer(_) => Some("integer"),
+        Expr::Bool(_) => Some("boolean"),
+        Expr::Array(_) => Some("array"),
+        Expr::Map(_) => Some("object"),
+    }
+}
+
+/// Joins doc comment lines into a single description, returning `None` if empty.
+fn join_doc(doc: &[&str]) -> Option<String> {
+    if doc.is_empty() {
+        return None;
+    }
+    let joined = doc.iter().map(|l| l.trim()).collect::<Vec<_>>().join("\n");
+    Some(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::meta::{Expr, Field, FieldKind, Integer, LeafKind, Meta};
+
+    #[test]
+    fn emits_required_default_and_type() {
+        static META: Meta = Meta {
+            name: "Conf",
+            doc: &[],
+            fields: &[
+                Field {
+                    name: "name",
+                    doc: &[" The name."],
+                    kind: FieldKind::Leaf {
+                        env: None,
+                        kind: LeafKind::Required { default: None },
+                    },
+                },
+                Field {
+                    name: "port",
+                    doc: &[],
+                    kind: FieldKind::Leaf {
+                        env: None,
+                        kind: LeafKind::Required {
+                            default: Some(Expr::Integer(Integer::U16(8080))),
+                        },
+                    },
+                },
+                Field {
+                    name: "opt",
+                    doc: &[],
+                    kind: FieldKind::Leaf { env: None, kind: LeafKind::Optional },
+                },
+            ],
+        };
+
+        let schema = generate(&META);
+        assert_eq!(schema["type"], "object");
+        // Only the no-default required leaf is listed as required.
+        assert_eq!(schema["required"], serde_json::json!(["name"]));
+        assert_eq!(schema["properties"]["name"]["description"], "The name.");
+        assert_eq!(schema["properties"]["port"]["type"], "integer");
+        assert_eq!(schema["properties"]["port"]["default"], 8080);
+        // Optional fields carry neither a required entry nor a type constraint.
+        assert!(schema["properties"]["opt"].get("type").is_none());
+    }
+}
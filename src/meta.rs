@@ -29,7 +29,7 @@ pub struct Field {
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FieldKind {
     Leaf {
-        env: Option<&'static str>,
+        env: Option<Env>,
         kind: LeafKind,
     },
     Nested {
@@ -37,6 +37,23 @@ pub enum FieldKind {
     },
 }
 
+/// Describes the environment variable a leaf field can be loaded from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Env {
+    /// Name of the environment variable, e.g. `APP_PORT`.
+    pub var: &'static str,
+
+    /// If set, the raw value is split on this separator and each element is
+    /// deserialized into the field's collection element type. Set via
+    /// `#[config(env_separator = ",")]` for list-valued variables.
+    pub separator: Option<&'static str>,
+
+    /// For map-valued fields, each element produced by `separator` is further
+    /// split on this separator into a key and a value. Set via
+    /// `#[config(env_kv_separator = "=")]`.
+    pub kv_separator: Option<&'static str>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LeafKind {
     /// A leaf field with a non `Option<_>` type.
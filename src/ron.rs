@@ -0,0 +1,184 @@
+//! RON specific features. This module only exists if the Cargo feature `ron`
+//! is enabled.
+
+use std::fmt::Write;
+
+use crate::meta::{Expr, Field, FieldKind, LeafKind, MapEntry, Meta};
+
+
+/// File extensions recognized as RON by the `Source` impls for `Path` and
+/// `PathBuf`.
+pub(crate) const FILE_EXTENSIONS: &[&str] = &["ron"];
+
+/// Options for generating a RON template.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FormatOptions {
+    /// Amount of indentation in spaces. Default: 4.
+    pub indent: u8,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent: 4 }
+    }
+}
+
+/// Generates a RON template from the given meta data.
+///
+/// The template contains all configuration values with their doc comments as
+/// `// ...` line comments. Fields with a default value are rendered with that
+/// value as RON literal; all other fields (required fields without a default
+/// and `Option<_>` fields) are commented out.
+pub fn template(meta: &Meta, options: FormatOptions) -> String {
+    let mut out = String::new();
+    format_doc(&mut out, 0, meta.doc, &options);
+    emit_struct(&mut out, meta, 0, &options);
+    out
+}
+
+fn emit_struct(out: &mut String, meta: &Meta, depth: usize, options: &FormatOptions) {
+    out.push_str("(\n");
+    for field in meta.fields {
+        emit_field(out, field, depth + 1, options);
+    }
+    indent(out, depth, options);
+    out.push(')');
+    // The root struct is terminated by a trailing newline, nested ones by the
+    // comma emitted by the caller.
+    if depth == 0 {
+        out.push('\n');
+    }
+}
+
+fn emit_field(out: &mut String, field: &Field, depth: usize, options: &FormatOptions) {
+    format_doc(out, depth, field.doc, options);
+    match &field.kind {
+        FieldKind::Nested { meta } => {
+            indent(out, depth, options);
+            let _ = write!(out, "{}: {}", field.name, meta.name);
+            emit_struct(out, meta, depth, options);
+            out.push_str(",\n");
+        }
+        FieldKind::Leaf { kind: LeafKind::Required { default: Some(expr) }, .. } => {
+            indent(out, depth, options);
+            let _ = write!(out, "{}: ", field.name);
+            emit_expr(out, expr);
+            out.push_str(",\n");
+        }
+        FieldKind::Leaf { .. } => {
+            indent(out, depth, options);
+            let _ = writeln!(out, "// {}: ,", field.name);
+        }
+    }
+}
+
+fn emit_expr(out: &mut String, expr: &Expr) {
+    match expr {
+        Expr::Str(s) => {
+            let _ = write!(out, "{:?}", s);
+        }
+        Expr::Float(v) => {
+            let _ = write!(out, "{}", v);
+        }
+        Expr::Integer(v) => {
+            let _ = write!(out, "{}", v);
+        }
+        Expr::Bool(b) => {
+            let _ = write!(out, "{}", b);
+        }
+        Expr::Array(elems) => {
+            out.push('[');
+            for (i, elem) in elems.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                emit_expr(out, elem);
+            }
+            out.push(']');
+        }
+        Expr::Map(entries) => {
+            out.push_str("{ ");
+            for (i, MapEntry { key, value }) in entries.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                emit_expr(out, &Expr::from(*key));
+                out.push_str(": ");
+                emit_expr(out, value);
+            }
+            out.push_str(" }");
+        }
+    }
+}
+
+fn format_doc(out: &mut String, depth: usize, doc: &[&str], options: &FormatOptions) {
+    for line in doc {
+        // `doc` entries keep their leading space from the source `/// ...`.
+        indent(out, depth, options);
+        let _ = writeln!(out, "//{}", line);
+    }
+}
+
+fn indent(out: &mut String, depth: usize, options: &FormatOptions) {
+    for _ in 0..depth * options.indent as usize {
+        out.push(' ');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{template, FormatOptions};
+    use crate::meta::{Expr, Field, FieldKind, Float, LeafKind, MapEntry, MapKey, Meta};
+
+    const fn leaf(default: Option<Expr>) -> FieldKind {
+        FieldKind::Leaf { env: None, kind: LeafKind::Required { default } }
+    }
+
+    #[test]
+    fn template_renders_maps_and_nested_structs() {
+        static INNER: Meta = Meta {
+            name: "Inner",
+            doc: &[],
+            fields: &[Field {
+                name: "score",
+                doc: &[" Scores."],
+                kind: FieldKind::Leaf {
+                    env: None,
+                    kind: LeafKind::Required {
+                        default: Some(Expr::Map(&[MapEntry {
+                            key: MapKey::Str("a"),
+                            value: Expr::Float(Float::F64(1.5)),
+                        }])),
+                    },
+                },
+            }],
+        };
+        static ROOT: Meta = Meta {
+            name: "Root",
+            doc: &[" Root doc."],
+            fields: &[
+                Field { name: "name", doc: &[], kind: leaf(Some(Expr::Str("site"))) },
+                Field {
+                    name: "list",
+                    doc: &[],
+                    kind: leaf(Some(Expr::Array(&[Expr::Str("a"), Expr::Str("b")]))),
+                },
+                Field { name: "inner", doc: &[], kind: FieldKind::Nested { meta: &INNER } },
+                Field { name: "opt", doc: &[], kind: leaf(None) },
+            ],
+        };
+
+        let expected = "// Root doc.\n\
+            (\n\
+            \x20   name: \"site\",\n\
+            \x20   list: [\"a\", \"b\"],\n\
+            \x20   inner: Inner(\n\
+            \x20       // Scores.\n\
+            \x20       score: { \"a\": 1.5 },\n\
+            \x20   ),\n\
+            \x20   // opt: ,\n\
+            )\n";
+        assert_eq!(template(&ROOT, FormatOptions::default()), expected);
+    }
+}